@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::{
+    any::{Any, TypeId},
     collections::{HashMap, HashSet},
     marker::PhantomData,
 };
@@ -17,7 +18,7 @@ use amethyst_derive::SystemDesc;
 use amethyst_input::{BindingTypes, InputHandler};
 use amethyst_window::ScreenDimensions;
 
-use crate::{targeted_below, Interactable, ScaleMode, UiEvent, UiEventType, UiTransform};
+use crate::{targeted_below, ScaleMode, UiEvent, UiEventType, UiTransform};
 
 /// Component that denotes whether a given ui widget is draggable.
 /// Requires UiTransform to work, and its expected way of usage is
@@ -29,18 +30,263 @@ impl Component for Draggable {
     type Storage = DenseVecStorage<Self>;
 }
 
+/// Resource controlling how `DragWidgetSystem` turns mouse motion into widget motion.
+#[derive(Debug, Clone, Copy)]
+pub struct DragSettings {
+    /// Factor applied to the per-frame mouse delta before it moves a dragged widget. `1.0`
+    /// (the default) is a no-op; `2.0` moves the widget twice as far as the cursor, `0.5` half
+    /// as far. Useful for fine-grained dragging or to compensate for high-DPI pointer deltas.
+    pub motion_scale: f32,
+
+    /// Distance, in pixels, the cursor must travel from the initial `ClickStart` position
+    /// before a `Draggable` actually starts dragging. Defaults to `0.0`, which starts the drag
+    /// on the first frame the mouse moves at all; a press and release without any motion is a
+    /// plain click and emits no `Dragging` or `Dropped` event.
+    pub drag_threshold: f32,
+}
+
+impl Default for DragSettings {
+    fn default() -> Self {
+        DragSettings {
+            motion_scale: 1.0,
+            drag_threshold: 0.0,
+        }
+    }
+}
+
+/// Arbitrary data that travels alongside a `Draggable` entity for the duration of a drag,
+/// borrowing the "any drag" pattern of carrying a type-erased payload rather than forcing
+/// consumers to encode drag data as raw entities. A `Droppable`'s accepted types are checked
+/// against this payload's concrete type when the drag ends.
+pub struct DragPayload(Box<dyn Any + Send + Sync>);
+
+impl Component for DragPayload {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl DragPayload {
+    /// Wraps `value` as a new drag payload.
+    pub fn new<T: Any + Send + Sync>(value: T) -> Self {
+        DragPayload(Box::new(value))
+    }
+
+    fn type_id(&self) -> TypeId {
+        (*self.0).type_id()
+    }
+}
+
+/// Component that marks a widget as a valid drop target, accepting drags whose `DragPayload`
+/// matches one of the registered types.
+///
+/// ```ignore
+/// Droppable::new().accepting::<InventorySlotId>()
+/// ```
+#[derive(Default)]
+pub struct Droppable {
+    accepted_types: HashSet<TypeId>,
+}
+
+impl Component for Droppable {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Droppable {
+    /// Creates a `Droppable` accepting no payload type until types are registered with
+    /// [`Droppable::accepting`]. Drags that carry no `DragPayload` are always accepted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `T` as an accepted payload type, returning `self` for chaining.
+    pub fn accepting<T: Any>(mut self) -> Self {
+        self.accepted_types.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Returns whether a payload of type `T` would be accepted.
+    pub fn accepts<T: Any>(&self) -> bool {
+        self.accepted_types.contains(&TypeId::of::<T>())
+    }
+
+    fn accepts_payload(&self, payload: &DragPayload) -> bool {
+        self.accepted_types.contains(&payload.type_id())
+    }
+}
+
+/// Axis a `DragConstraint` may lock a drag's motion to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DragAxis {
+    /// Only `local_x` may change; `local_y` stays fixed.
+    Horizontal,
+    /// Only `local_y` may change; `local_x` stays fixed.
+    Vertical,
+    /// Both axes may change freely.
+    Both,
+}
+
+/// Coordinate space a `DragConstraint`'s bounds are expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ConstraintSpace {
+    /// Bounds are in the dragged widget's own `local_x`/`local_y` space.
+    Parent,
+    /// Bounds are in screen space, e.g. to confine a window to the visible viewport
+    /// regardless of where its parent sits.
+    Screen,
+}
+
+/// Component restricting how a `Draggable` widget may move: locking motion to a single axis,
+/// clamping the resulting position to a rectangle, and/or snapping it to a grid. Covers
+/// sliders (axis lock), windows confined to the screen (bounds), and snap-to-grid editors
+/// (grid snap) with a single component.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DragConstraint {
+    /// Axis motion is locked to.
+    pub axis: DragAxis,
+    /// Inclusive `(min, max)` rectangle the dragged position is clamped to, and the space it's
+    /// expressed in. Clamping happens before `grid_snap`, so a snapped position can land
+    /// outside bounds that are not multiples of the snap step.
+    pub bounds: Option<(ConstraintSpace, Vector2<f32>, Vector2<f32>)>,
+    /// When set, the resulting `local_x`/`local_y` are rounded to the nearest multiple of this
+    /// step size.
+    pub grid_snap: Option<f32>,
+}
+
+impl Component for DragConstraint {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Default for DragConstraint {
+    fn default() -> Self {
+        DragConstraint {
+            axis: DragAxis::Both,
+            bounds: None,
+            grid_snap: None,
+        }
+    }
+}
+
+impl DragConstraint {
+    /// Creates an unconstrained `DragConstraint`; configure it with the `with_*` methods.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locks motion to `axis`, returning `self` for chaining.
+    pub fn with_axis(mut self, axis: DragAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Clamps the dragged position to `[min, max]`, expressed in `space`, returning `self` for
+    /// chaining.
+    pub fn with_bounds(
+        mut self,
+        space: ConstraintSpace,
+        min: Vector2<f32>,
+        max: Vector2<f32>,
+    ) -> Self {
+        self.bounds = Some((space, min, max));
+        self
+    }
+
+    /// Snaps the dragged position to the nearest multiple of `step`, returning `self` for
+    /// chaining.
+    pub fn with_grid_snap(mut self, step: f32) -> Self {
+        self.grid_snap = Some(step);
+        self
+    }
+
+    /// Applies this constraint to a candidate `(local_x, local_y)`, given the widget's
+    /// transform prior to this frame's move; its global and local positions are used to
+    /// interpret `ConstraintSpace::Screen` bounds.
+    fn apply(&self, ui_transform: &UiTransform, candidate_x: f32, candidate_y: f32) -> (f32, f32) {
+        let (allow_x, allow_y) = match self.axis {
+            DragAxis::Horizontal => (true, false),
+            DragAxis::Vertical => (false, true),
+            DragAxis::Both => (true, true),
+        };
+
+        let mut x = if allow_x {
+            candidate_x
+        } else {
+            ui_transform.local_x
+        };
+        let mut y = if allow_y {
+            candidate_y
+        } else {
+            ui_transform.local_y
+        };
+
+        // Bounds and grid snap only ever touch an axis the drag is actually allowed to move
+        // on: the locked axis stays exactly at its current value, so an axis-locked drag (e.g.
+        // a horizontal slider) never gets nudged off its track.
+        if let Some((space, min, max)) = &self.bounds {
+            let (min, max) = match space {
+                ConstraintSpace::Parent => (*min, *max),
+                ConstraintSpace::Screen => {
+                    // Bounds are expressed in screen space; translate them into the widget's
+                    // local space using the offset between its current global and local
+                    // position.
+                    let offset_x = ui_transform.global_x - ui_transform.local_x;
+                    let offset_y = ui_transform.global_y - ui_transform.local_y;
+                    (
+                        Vector2::new(min[0] - offset_x, min[1] - offset_y),
+                        Vector2::new(max[0] - offset_x, max[1] - offset_y),
+                    )
+                }
+            };
+            if allow_x {
+                x = x.max(min[0]).min(max[0]);
+            }
+            if allow_y {
+                y = y.max(min[1]).min(max[1]);
+            }
+        }
+
+        if let Some(step) = self.grid_snap {
+            if step > 0.0 {
+                if allow_x {
+                    x = (x / step).round() * step;
+                }
+                if allow_y {
+                    y = (y / step).round() * step;
+                }
+            }
+        }
+
+        (x, y)
+    }
+}
+
+/// Tracks a single entity's progress through a drag gesture, from the initial press up to
+/// (and including) an active drag.
+#[derive(Debug)]
+enum DragState {
+    /// `ClickStart` happened at `start`, but the cursor hasn't yet crossed
+    /// `DragSettings::drag_threshold`. No `Dragging` events are emitted and `UiTransform` is
+    /// left untouched while pending; a `ClickStop` in this state is a plain click.
+    Pending { start: Vector2<f32> },
+    /// The threshold has been crossed and the widget is actively being dragged.
+    Dragging {
+        /// The widget's `local_x`/`local_y` at the moment dragging began, used to compute
+        /// `offset_from_mouse` against the (possibly constrained) current position.
+        start_local: Vector2<f32>,
+        /// The mouse position one frame ago.
+        prev: Vector2<f32>,
+        /// The topmost `Droppable` currently hovered by the drag, if any.
+        hovered: Option<Entity>,
+    },
+}
+
 #[derive(Debug, SystemDesc)]
 #[system_desc(name(DragWidgetSystemDesc))]
 pub struct DragWidgetSystem<T: BindingTypes> {
     #[system_desc(event_channel_reader)]
     ui_reader_id: ReaderId<UiEvent>,
 
-    /// hashmap whose keys are every entities being dragged,
-    /// and whose element is a tuple whose first element is
-    /// the original mouse position when drag first started,
-    /// and second element the mouse position one frame ago
+    /// Every entity currently mid-gesture, from pending click through active drag.
     #[system_desc(skip)]
-    record: HashMap<Entity, (Vector2<f32>, Vector2<f32>)>,
+    record: HashMap<Entity, DragState>,
 
     phantom: PhantomData<T>,
 }
@@ -67,10 +313,13 @@ where
         Read<'s, InputHandler<T>>,
         ReadExpect<'s, ScreenDimensions>,
         ReadExpect<'s, ParentHierarchy>,
+        Read<'s, DragSettings>,
         ReadStorage<'s, Hidden>,
         ReadStorage<'s, HiddenPropagate>,
         ReadStorage<'s, Draggable>,
-        ReadStorage<'s, Interactable>,
+        ReadStorage<'s, DragPayload>,
+        ReadStorage<'s, Droppable>,
+        ReadStorage<'s, DragConstraint>,
         Write<'s, EventChannel<UiEvent>>,
         WriteStorage<'s, UiTransform>,
     );
@@ -82,10 +331,13 @@ where
             input_handler,
             screen_dimensions,
             hierarchy,
+            drag_settings,
             hiddens,
             hidden_props,
             draggables,
-            interactables,
+            payloads,
+            droppables,
+            constraints,
             mut ui_events,
             mut ui_transforms,
         ): Self::SystemData,
@@ -99,7 +351,8 @@ where
             match event.event_type {
                 UiEventType::ClickStart => {
                     if draggables.get(event.target).is_some() {
-                        self.record.insert(event.target, (mouse_pos, mouse_pos));
+                        self.record
+                            .insert(event.target, DragState::Pending { start: mouse_pos });
                     }
                 }
                 UiEventType::ClickStop => {
@@ -117,46 +370,158 @@ where
             }
         }
 
-        for (entity, (first, prev)) in self.record.iter_mut() {
-            ui_events.single_write(UiEvent::new(
-                UiEventType::Dragging {
-                    offset_from_mouse: mouse_pos - *first,
-                    new_position: mouse_pos,
-                },
-                *entity,
-            ));
+        for (entity, state) in self.record.iter_mut() {
+            if let DragState::Pending { start } = *state {
+                if (mouse_pos - start).norm() <= drag_settings.drag_threshold {
+                    continue;
+                }
+                let current = ui_transforms.get(*entity).unwrap();
+                *state = DragState::Dragging {
+                    start_local: Vector2::new(current.local_x, current.local_y),
+                    prev: mouse_pos,
+                    hovered: None,
+                };
+            }
+
+            let (start_local, prev, hovered) = match state {
+                DragState::Dragging {
+                    start_local,
+                    prev,
+                    hovered,
+                } => (start_local, prev, hovered),
+                DragState::Pending { .. } => continue,
+            };
 
             let change = mouse_pos - *prev;
+            *prev = mouse_pos;
 
             let (scale_x, scale_y) =
                 get_scale_for_entity(*entity, &hierarchy, &ui_transforms, &screen_dimensions);
 
             let ui_transform = ui_transforms.get_mut(*entity).unwrap();
-            ui_transform.local_x += scale_x * change[0];
-            ui_transform.local_y += scale_y * change[1];
+            let candidate_x =
+                ui_transform.local_x + scale_x * change[0] * drag_settings.motion_scale;
+            let candidate_y =
+                ui_transform.local_y + scale_y * change[1] * drag_settings.motion_scale;
 
-            *prev = mouse_pos;
-        }
+            let (new_x, new_y) = match constraints.get(*entity) {
+                Some(constraint) => constraint.apply(ui_transform, candidate_x, candidate_y),
+                None => (candidate_x, candidate_y),
+            };
 
-        for entity in click_stopped.iter() {
+            ui_transform.local_x = new_x;
+            ui_transform.local_y = new_y;
+
+            let new_position = Vector2::new(new_x, new_y);
             ui_events.single_write(UiEvent::new(
-                UiEventType::Dropped {
-                    dropped_on: targeted_below(
-                        (mouse_pos[0], mouse_pos[1]),
-                        ui_transforms.get(*entity).unwrap().global_z,
-                        (
-                            &*entities,
-                            &ui_transforms,
-                            interactables.maybe(),
-                            !&hiddens,
-                            !&hidden_props,
-                        )
-                            .join(),
-                    ),
+                UiEventType::Dragging {
+                    offset_from_mouse: new_position - *start_local,
+                    new_position,
                 },
                 *entity,
             ));
 
+            // Hover against the same `&droppables` set the drop itself resolves against
+            // (see the `click_stopped` loop below), so the widget that lights up on
+            // `DragEnter` is always the one a `Dropped`/`DropRejected` will land on.
+            let target_below = targeted_below(
+                (mouse_pos[0], mouse_pos[1]),
+                ui_transforms.get(*entity).unwrap().global_z,
+                (
+                    &*entities,
+                    &ui_transforms,
+                    &droppables,
+                    !&hiddens,
+                    !&hidden_props,
+                )
+                    .join(),
+            );
+
+            if target_below != *hovered {
+                if let Some(left) = *hovered {
+                    ui_events.single_write(UiEvent::new(
+                        UiEventType::DragLeave {
+                            dragged: *entity,
+                            target: left,
+                        },
+                        left,
+                    ));
+                }
+                if let Some(entered) = target_below {
+                    ui_events.single_write(UiEvent::new(
+                        UiEventType::DragEnter {
+                            dragged: *entity,
+                            target: entered,
+                        },
+                        entered,
+                    ));
+                }
+                *hovered = target_below;
+            }
+        }
+
+        for entity in click_stopped.iter() {
+            let hovered = match self.record.get(entity) {
+                Some(DragState::Pending { .. }) => {
+                    self.record.remove(entity);
+                    continue;
+                }
+                Some(DragState::Dragging { hovered, .. }) => *hovered,
+                None => continue,
+            };
+
+            // Resolve the topmost entity that actually has a `Droppable` (same set the hover
+            // tracking above uses), not merely the topmost widget: a drop target (e.g. an
+            // inventory slot) may sit beneath a non-droppable widget (e.g. the item icon it
+            // holds).
+            let dropped_on = targeted_below(
+                (mouse_pos[0], mouse_pos[1]),
+                ui_transforms.get(*entity).unwrap().global_z,
+                (
+                    &*entities,
+                    &ui_transforms,
+                    &droppables,
+                    !&hiddens,
+                    !&hidden_props,
+                )
+                    .join(),
+            );
+
+            let payload = payloads.get(*entity);
+
+            let event_type = match dropped_on {
+                Some(target) => {
+                    let droppable = droppables.get(target).unwrap();
+                    if payload.map_or(true, |p| droppable.accepts_payload(p)) {
+                        UiEventType::Dropped {
+                            dropped_on: Some(target),
+                            accepted: true,
+                        }
+                    } else {
+                        UiEventType::DropRejected { dropped_on: target }
+                    }
+                }
+                None => UiEventType::Dropped {
+                    dropped_on: None,
+                    accepted: payload.is_none(),
+                },
+            };
+
+            ui_events.single_write(UiEvent::new(event_type, *entity));
+
+            // The drop zone that lit up on `DragEnter` needs an explicit `DragLeave` to
+            // un-highlight, since `Dropped`/`DropRejected` fire on the dragged entity, not on
+            // the hovered target.
+            if let Some(target) = hovered {
+                ui_events.single_write(UiEvent::new(
+                    UiEventType::DragLeave {
+                        dragged: *entity,
+                        target,
+                    },
+                    target,
+                ));
+            }
+
             self.record.remove(entity);
         }
     }
@@ -0,0 +1,76 @@
+use amethyst_core::{ecs::Entity, math::Vector2};
+
+/// An event that's fired when something happens to a ui widget, for example clicking,
+/// hovering, or dragging.
+#[derive(Debug, Clone)]
+pub struct UiEvent {
+    /// The type of event.
+    pub event_type: UiEventType,
+    /// The entity on which the event happened.
+    pub target: Entity,
+}
+
+impl UiEvent {
+    /// Creates a new `UiEvent`.
+    pub fn new(event_type: UiEventType, target: Entity) -> Self {
+        UiEvent { event_type, target }
+    }
+}
+
+/// The type of ui event.
+#[derive(Debug, Clone)]
+pub enum UiEventType {
+    /// Click started on the target entity.
+    ClickStart,
+    /// Click ended, whether or not it started on this entity.
+    ClickStop,
+    /// The cursor started hovering over the target entity.
+    HoverStart,
+    /// The cursor stopped hovering over the target entity.
+    HoverStop,
+    /// A value carried by the target entity's widget has changed.
+    ValueChange,
+    /// Fired once per frame, for as long as a `Draggable` widget is being dragged.
+    Dragging {
+        /// Offset between the dragged widget's (possibly scaled and constrained) position for
+        /// this frame and the position it was at when the drag started. Follows `new_position`
+        /// rather than the raw mouse delta, so it only matches mouse movement 1:1 when
+        /// `DragSettings::motion_scale` is `1.0`, no `DragConstraint` is in effect and the
+        /// widget uses `ScaleMode::Pixel`.
+        offset_from_mouse: Vector2<f32>,
+        /// The widget's `local_x`/`local_y` for this frame, after applying
+        /// `DragSettings::motion_scale` and any `DragConstraint`.
+        new_position: Vector2<f32>,
+    },
+    /// Fired on the dragged entity when a drag ends.
+    Dropped {
+        /// The topmost `Droppable` under the cursor when the drag ended, if any.
+        dropped_on: Option<Entity>,
+        /// Whether `dropped_on` (if any) accepted the drag's payload. Always `true` when the
+        /// dragged entity carries no `DragPayload`, since there is nothing to reject.
+        accepted: bool,
+    },
+    /// Fired on the dragged entity instead of `Dropped` when the drag ended over a `Droppable`
+    /// that does not accept the dragged entity's `DragPayload` type.
+    DropRejected {
+        /// The `Droppable` entity that rejected the drag.
+        dropped_on: Entity,
+    },
+    /// Fired on `target` when a drag starts hovering over it, `target` being the topmost
+    /// visible `Droppable` under the cursor.
+    DragEnter {
+        /// The entity being dragged.
+        dragged: Entity,
+        /// The entity newly hovered over.
+        target: Entity,
+    },
+    /// Fired on `target` when a drag stops hovering over it, either because another
+    /// `Droppable` became topmost, the cursor moved off every `Droppable`, `target` became
+    /// hidden, or the drag ended.
+    DragLeave {
+        /// The entity being dragged.
+        dragged: Entity,
+        /// The entity no longer hovered over.
+        target: Entity,
+    },
+}